@@ -0,0 +1,178 @@
+use crate::numeric::Numeric;
+use crate::Message;
+
+/// A structured view over a message's command and params, so callers can
+/// `match` on well-known commands instead of string-comparing
+/// `message.command()`. Three-digit commands are numerics (see
+/// [`crate::numeric`]) and are kept apart from word commands, per the
+/// grammar's `<command> ::= <letter> { <letter> } | <number> <number>
+/// <number>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command<'a> {
+    PrivMsg { target: &'a str, text: &'a str },
+    Notice { target: &'a str, text: &'a str },
+    Join(&'a str),
+    Part(&'a str, Option<&'a str>),
+    Ping(&'a str),
+    Pong(&'a str),
+    Nick(&'a str),
+    Quit(Option<&'a str>),
+    Numeric(Numeric, Vec<&'a str>),
+    Unknown(&'a str, Vec<&'a str>),
+}
+
+impl<'a> Command<'a> {
+    /// Builds a `Command` from a parsed message's raw command word and
+    /// params, defaulting any missing param to an empty string rather than
+    /// panicking on malformed input.
+    pub fn from_message(message: &Message<'a>) -> Command<'a> {
+        let command = message.command().unwrap_or("");
+        let params = message.params().unwrap_or_default();
+
+        if command.len() == 3 && command.bytes().all(|b| b.is_ascii_digit()) {
+            let code: u16 = command.parse().unwrap_or(0);
+            return Command::Numeric(Numeric::from(code), params);
+        }
+
+        match command {
+            "PRIVMSG" => Command::PrivMsg {
+                target: params.first().copied().unwrap_or(""),
+                text: params.get(1).copied().unwrap_or(""),
+            },
+            "NOTICE" => Command::Notice {
+                target: params.first().copied().unwrap_or(""),
+                text: params.get(1).copied().unwrap_or(""),
+            },
+            "JOIN" => Command::Join(params.first().copied().unwrap_or("")),
+            "PART" => Command::Part(params.first().copied().unwrap_or(""), params.get(1).copied()),
+            "PING" => Command::Ping(params.first().copied().unwrap_or("")),
+            "PONG" => Command::Pong(params.first().copied().unwrap_or("")),
+            "NICK" => Command::Nick(params.first().copied().unwrap_or("")),
+            "QUIT" => Command::Quit(params.first().copied()),
+            other => Command::Unknown(other, params),
+        }
+    }
+
+    /// The wire command word for this variant, e.g. `"PRIVMSG"` or the
+    /// three-digit form of a [`Command::Numeric`].
+    pub fn name(&self) -> String {
+        match self {
+            Command::PrivMsg { .. } => "PRIVMSG".to_string(),
+            Command::Notice { .. } => "NOTICE".to_string(),
+            Command::Join(_) => "JOIN".to_string(),
+            Command::Part(_, _) => "PART".to_string(),
+            Command::Ping(_) => "PING".to_string(),
+            Command::Pong(_) => "PONG".to_string(),
+            Command::Nick(_) => "NICK".to_string(),
+            Command::Quit(_) => "QUIT".to_string(),
+            Command::Numeric(numeric, _) => format!("{:03}", numeric.code()),
+            Command::Unknown(command, _) => command.to_string(),
+        }
+    }
+
+    /// The params this variant would serialize to, in wire order.
+    pub fn params(&self) -> Vec<&'a str> {
+        match self {
+            Command::PrivMsg { target, text } => vec![target, text],
+            Command::Notice { target, text } => vec![target, text],
+            Command::Join(channel) => vec![channel],
+            Command::Part(channel, None) => vec![channel],
+            Command::Part(channel, Some(reason)) => vec![channel, reason],
+            Command::Ping(token) => vec![token],
+            Command::Pong(token) => vec![token],
+            Command::Nick(name) => vec![name],
+            Command::Quit(None) => vec![],
+            Command::Quit(Some(msg)) => vec![msg],
+            Command::Numeric(_, params) => params.clone(),
+            Command::Unknown(_, params) => params.clone(),
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// The structured [`Command`] for this message's command word and
+    /// params. See [`Message::command`] for the raw command word.
+    pub fn typed_command(&self) -> Command<'a> {
+        Command::from_message(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use crate::Message;
+
+    #[test]
+    fn privmsg() {
+        let parsed = Message::parse("PRIVMSG #715209 :hello").unwrap();
+
+        assert_eq!(
+            parsed.typed_command(),
+            Command::PrivMsg {
+                target: "#715209",
+                text: "hello"
+            }
+        );
+    }
+
+    #[test]
+    fn part_without_reason() {
+        let parsed = Message::parse("PART #715209").unwrap();
+
+        assert_eq!(parsed.typed_command(), Command::Part("#715209", None));
+    }
+
+    #[test]
+    fn part_with_reason() {
+        let parsed = Message::parse("PART #715209 :goodbye").unwrap();
+
+        assert_eq!(
+            parsed.typed_command(),
+            Command::Part("#715209", Some("goodbye"))
+        );
+    }
+
+    #[test]
+    fn numeric_resolves_to_named_variant() {
+        let parsed = Message::parse(":tmi.twitch.tv 001 715209 :Welcome").unwrap();
+
+        assert_eq!(
+            parsed.typed_command(),
+            Command::Numeric(crate::numeric::Numeric::RplWelcome, vec!["715209", "Welcome"])
+        );
+    }
+
+    #[test]
+    fn numeric_falls_back_to_raw_code_when_unknown() {
+        let parsed = Message::parse(":tmi.twitch.tv 999 715209 :mystery").unwrap();
+
+        assert_eq!(
+            parsed.typed_command(),
+            Command::Numeric(
+                crate::numeric::Numeric::Unknown(999),
+                vec!["715209", "mystery"]
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_command_keeps_its_params() {
+        let parsed = Message::parse("CAP * ACK :twitch.tv/tags").unwrap();
+
+        assert_eq!(
+            parsed.typed_command(),
+            Command::Unknown("CAP", vec!["*", "ACK", "twitch.tv/tags"])
+        );
+    }
+
+    #[test]
+    fn name_and_params_reconstruct_the_command() {
+        let command = Command::PrivMsg {
+            target: "#715209",
+            text: "hello",
+        };
+
+        assert_eq!(command.name(), "PRIVMSG");
+        assert_eq!(command.params(), vec!["#715209", "hello"]);
+    }
+}
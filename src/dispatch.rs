@@ -0,0 +1,133 @@
+use crate::command::Command;
+use crate::error::ParseError;
+use crate::Message;
+use crate::numeric::Numeric;
+
+/// Receives every parsed message a [`Dispatcher`] sees, after its built-in
+/// responders have had a chance to react. Implement this for your bot's
+/// state (nick tracking, channel state, whatever you need) instead of
+/// hand-rolling the PING/PONG keepalive yourself. The dispatcher does not
+/// reconnect: a `QUIT` is only passed on to the handler.
+pub trait Handler {
+    fn on_message(&mut self, message: &Message<'_>);
+}
+
+/// A small event-dispatch layer on top of [`Message`] that knows the
+/// connection-handling song-and-dance every IRC bot needs: answering
+/// `PING` with `PONG`, joining its channels once the server sends
+/// `RPL_WELCOME`, and retrying with an underscore-suffixed nick on
+/// `ERR_NICKNAMEINUSE`. Everything else (including `KICK`, `INVITE` and
+/// `QUIT`) is simply forwarded to the [`Handler`].
+pub struct Dispatcher<H: Handler> {
+    nick: String,
+    channels: Vec<String>,
+    handler: H,
+}
+
+impl<H: Handler> Dispatcher<H> {
+    pub fn new(nick: impl Into<String>, channels: Vec<String>, handler: H) -> Dispatcher<H> {
+        Dispatcher {
+            nick: nick.into(),
+            channels,
+            handler,
+        }
+    }
+
+    pub fn nick(&self) -> &str {
+        &self.nick
+    }
+
+    /// Parses one line and reacts to it: runs the built-in responders, then
+    /// passes the message to the [`Handler`]. Returns the wire-format lines
+    /// (if any) the built-in responders want written back to the
+    /// connection, in the order they should be sent.
+    pub fn dispatch(&mut self, line: &str) -> Result<Vec<String>, ParseError> {
+        let message = Message::parse(line)?;
+        let mut outgoing = Vec::new();
+
+        match message.typed_command() {
+            Command::Ping(token) => outgoing.push(format!("PONG :{}\r\n", token)),
+            Command::Numeric(Numeric::RplWelcome, _) => {
+                for channel in &self.channels {
+                    outgoing.push(format!("JOIN {}\r\n", channel));
+                }
+            }
+            Command::Numeric(Numeric::ErrNicknameinuse, _) => {
+                self.nick.push('_');
+                outgoing.push(format!("NICK {}\r\n", self.nick));
+            }
+            _ => {}
+        }
+
+        self.handler.on_message(&message);
+
+        Ok(outgoing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dispatcher, Handler};
+    use crate::Message;
+
+    #[derive(Default)]
+    struct Seen(Vec<String>);
+
+    impl Handler for Seen {
+        fn on_message(&mut self, message: &Message<'_>) {
+            if let Some(command) = message.command() {
+                self.0.push(command.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn ping_triggers_pong() {
+        let mut dispatcher = Dispatcher::new("715209", vec![], Seen::default());
+
+        let outgoing = dispatcher.dispatch("PING :tmi.twitch.tv").unwrap();
+
+        assert_eq!(outgoing, vec!["PONG :tmi.twitch.tv\r\n".to_string()]);
+    }
+
+    #[test]
+    fn welcome_joins_configured_channels() {
+        let mut dispatcher = Dispatcher::new(
+            "715209",
+            vec!["#715209".to_string(), "#other".to_string()],
+            Seen::default(),
+        );
+
+        let outgoing = dispatcher
+            .dispatch(":tmi.twitch.tv 001 715209 :Welcome")
+            .unwrap();
+
+        assert_eq!(
+            outgoing,
+            vec!["JOIN #715209\r\n".to_string(), "JOIN #other\r\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn nickname_in_use_retries_with_underscore() {
+        let mut dispatcher = Dispatcher::new("715209", vec![], Seen::default());
+
+        let outgoing = dispatcher
+            .dispatch(":tmi.twitch.tv 433 * 715209 :Nickname is already in use")
+            .unwrap();
+
+        assert_eq!(outgoing, vec!["NICK 715209_\r\n".to_string()]);
+        assert_eq!(dispatcher.nick(), "715209_");
+    }
+
+    #[test]
+    fn handler_still_sees_unhandled_commands() {
+        let mut dispatcher = Dispatcher::new("715209", vec![], Seen::default());
+
+        dispatcher
+            .dispatch(":715209!715209@715209.tmi.twitch.tv KICK #715209 other :bye")
+            .unwrap();
+
+        assert_eq!(dispatcher.handler.0, vec!["KICK".to_string()]);
+    }
+}
@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why [`crate::Message::parse`] failed, with the byte offset into the
+/// input where the problem was found so callers can point at the bad
+/// input instead of just knowing parsing failed somewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input was empty.
+    Empty,
+    /// Tags or a prefix were found, but no command followed.
+    MissingCommand { offset: usize },
+    /// The segment after `:` had more `!`/`@` delimiters than a
+    /// `<nick> ['!' <user>] ['@' <host>]` prefix can have.
+    MalformedPrefix { offset: usize },
+    /// A tag had no key (e.g. an empty `;;` segment, or one starting with
+    /// `=`).
+    MalformedTag { key: String, offset: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "nothing found to parse"),
+            ParseError::MissingCommand { offset } => {
+                write!(f, "no command found (at byte {})", offset)
+            }
+            ParseError::MalformedPrefix { offset } => {
+                write!(f, "malformed prefix at byte {}", offset)
+            }
+            ParseError::MalformedTag { key, offset } => {
+                write!(f, "malformed tag `{}` at byte {}", key, offset)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::error::ParseError;
+use crate::prefix::{OwnedPrefix, Prefix};
+use crate::tags::Tags;
+
+/// A parsed IRC message, borrowing directly from the line it was parsed
+/// from. Rather than allocating a `String`/`Vec<String>` per field, `parse`
+/// only records where each field lives in `raw` and accessors slice into it
+/// on demand. The one allocation `parse` makes is the `Vec` of param ranges,
+/// for a message that has params.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message<'a> {
+    raw: &'a str,
+    tags: Option<Range<usize>>,
+    prefix: Option<Range<usize>>,
+    command: Option<Range<usize>>,
+    params: Option<Vec<Range<usize>>>,
+    /// Whether the last entry in `params` came from a `:`-prefixed trailing
+    /// segment in the source, as opposed to a plain middle param. Needed by
+    /// `Display` to know whether to re-add the `:` on serialization.
+    trailing_param: bool,
+}
+
+impl<'a> Message<'a> {
+    pub fn parse(message: &'a str) -> Result<Message<'a>, ParseError> {
+        if message.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut msg = Message {
+            raw: message,
+            tags: None,
+            prefix: None,
+            command: None,
+            params: None,
+            trailing_param: false,
+        };
+        let mut pos_head = 0;
+        let mut pos_tail;
+
+        if message.starts_with('@') {
+            pos_tail = match message.find(' ') {
+                Some(i) => i,
+                None => {
+                    return Err(ParseError::MissingCommand {
+                        offset: message.len(),
+                    })
+                }
+            };
+
+            for kv in message[1..pos_tail].split(';') {
+                let key = kv.split('=').next().unwrap_or("");
+                if key.is_empty() {
+                    return Err(ParseError::MalformedTag {
+                        key: key.to_string(),
+                        offset: kv.as_ptr() as usize - message.as_ptr() as usize,
+                    });
+                }
+            }
+
+            msg.tags = Some(1..pos_tail);
+            pos_head = pos_tail + 1;
+        }
+
+        if message[pos_head..].starts_with(':') {
+            pos_tail = match message[pos_head..].find(' ') {
+                Some(i) => pos_head + i,
+                None => {
+                    return Err(ParseError::MissingCommand {
+                        offset: message.len(),
+                    })
+                }
+            };
+
+            let prefix_raw = &message[pos_head + 1..pos_tail];
+            let delimiters = prefix_raw.chars().filter(|c| *c == '!' || *c == '@').count();
+            if delimiters > 2 {
+                return Err(ParseError::MalformedPrefix { offset: pos_head });
+            }
+
+            msg.prefix = Some(pos_head + 1..pos_tail);
+            pos_head = pos_tail + 1;
+        }
+
+        let command_and_params = &message[pos_head..];
+        if command_and_params.is_empty() {
+            return Err(ParseError::MissingCommand { offset: pos_head });
+        }
+
+        if let Some(i) = command_and_params.find(' ') {
+            msg.command = Some(pos_head..pos_head + i);
+
+            let params_start = pos_head + i + 1;
+            let params_string = &message[params_start..];
+            // A `:` only introduces the trailing param when it starts
+            // `params_string` or follows a space; a `:` inside a middle
+            // param (e.g. `a:b`) is just part of that param's text.
+            let text_loc = if params_string.starts_with(':') {
+                Some(0)
+            } else {
+                params_string.find(" :").map(|space| space + 1)
+            };
+            let mut params: Vec<Range<usize>> = Vec::new();
+
+            match text_loc {
+                Some(0) => {
+                    params.push(params_start + 1..message.len());
+                    msg.trailing_param = true;
+                }
+                Some(loc) => {
+                    // `loc` always points right after an ASCII space (or at
+                    // index 0), so it's a char-boundary-safe index to slice
+                    // on regardless of what precedes it. Including the
+                    // trailing separator space (if any) here is harmless
+                    // since `split_ascii_whitespace` ignores it.
+                    for word in params_string[..loc].split_ascii_whitespace() {
+                        let start = word.as_ptr() as usize - message.as_ptr() as usize;
+                        params.push(start..start + word.len());
+                    }
+                    params.push(params_start + loc + 1..message.len());
+                    msg.trailing_param = true;
+                }
+                None => {
+                    for word in params_string.split_ascii_whitespace() {
+                        let start = word.as_ptr() as usize - message.as_ptr() as usize;
+                        params.push(start..start + word.len());
+                    }
+                }
+            }
+
+            msg.params = Some(params);
+        } else {
+            msg.command = Some(pos_head..message.len());
+        }
+
+        Ok(msg)
+    }
+
+    /// The raw line this message was parsed from.
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    pub fn tags(&self) -> Option<Tags<'a>> {
+        self.tags.clone().map(|r| Tags::new(&self.raw[r]))
+    }
+
+    pub fn prefix(&self) -> Option<Prefix<'a>> {
+        self.prefix.clone().map(|r| Prefix::parse(&self.raw[r]))
+    }
+
+    pub fn command(&self) -> Option<&'a str> {
+        self.command.clone().map(|r| &self.raw[r])
+    }
+
+    pub fn params(&self) -> Option<Vec<&'a str>> {
+        self.params
+            .as_ref()
+            .map(|ranges| ranges.iter().map(|r| &self.raw[r.clone()]).collect())
+    }
+
+    /// Copies every field out into a fully owned, `'static` message, for
+    /// callers that need to hold onto it past the lifetime of `raw`.
+    pub fn to_owned(&self) -> OwnedMessage {
+        OwnedMessage {
+            tags: self.tags().map(|tags| {
+                tags.iter()
+                    .map(|(k, v)| (k.to_string(), v.map(|v| v.to_string())))
+                    .collect()
+            }),
+            prefix: self.prefix().map(|p| p.to_owned()),
+            command: self.command().map(|c| c.to_string()),
+            params: self
+                .params()
+                .map(|params| params.into_iter().map(|p| p.to_string()).collect()),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Message<'a> {
+    /// Reassembles the message into its wire format, i.e. the inverse of
+    /// [`Message::parse`]. Tags are only emitted when present, and the final
+    /// param is written trailing-style (`:`-prefixed) when it was parsed
+    /// from a trailing segment, or when it's empty or contains a space (the
+    /// only cases where a plain middle param couldn't round-trip as-is).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(tags) = self.tags() {
+            write!(f, "@")?;
+            for (i, (key, value)) in tags.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ";")?;
+                }
+                match value {
+                    Some(value) => write!(f, "{}={}", key, crate::tags::escape(&value))?,
+                    None => write!(f, "{}", key)?,
+                }
+            }
+            write!(f, " ")?;
+        }
+
+        if let Some(prefix) = self.prefix() {
+            match prefix {
+                Prefix::Servername(name) => write!(f, ":{} ", name)?,
+                Prefix::Nick(nick, user, host) => {
+                    write!(f, ":{}", nick)?;
+                    if let Some(user) = user {
+                        write!(f, "!{}", user)?;
+                    }
+                    if let Some(host) = host {
+                        write!(f, "@{}", host)?;
+                    }
+                    write!(f, " ")?;
+                }
+            }
+        }
+
+        if let Some(command) = self.command() {
+            write!(f, "{}", command)?;
+        }
+
+        if let Some(params) = self.params() {
+            if !params.is_empty() {
+                let last = params.len() - 1;
+                for (i, param) in params.iter().enumerate() {
+                    let trailing_style = i == last
+                        && (self.trailing_param || param.is_empty() || param.contains(' '));
+                    if trailing_style {
+                        write!(f, " :{}", param)?;
+                    } else {
+                        write!(f, " {}", param)?;
+                    }
+                }
+            }
+        }
+
+        write!(f, "\r\n")
+    }
+}
+
+/// A fully owned copy of a [`Message`], for callers that need a `'static`
+/// value (e.g. to stash in a queue) instead of borrowing from the input
+/// buffer.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OwnedMessage {
+    pub tags: Option<HashMap<String, Option<String>>>,
+    pub prefix: Option<OwnedPrefix>,
+    pub command: Option<String>,
+    pub params: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Message;
+    use crate::error::ParseError;
+    use crate::prefix::Prefix;
+
+    #[test]
+    fn normal_message() {
+        let parsed = Message::parse("@badge-info=;badges=broadcaster/1;color=#008000;display-name=715209;emotes=;flags=;id=8a90aa05-eea3-4699-84eb-1d4c65b85f94;mod=0;room-id=21621987;subscriber=0;tmi-sent-ts=1559891010190;turbo=0;user-id=21621987;user-type= :715209!715209@715209.tmi.twitch.tv PRIVMSG #715209 :hello").unwrap();
+
+        assert!(parsed.tags().is_some());
+        assert_eq!(
+            parsed.prefix(),
+            Some(Prefix::Nick(
+                "715209",
+                Some("715209"),
+                Some("715209.tmi.twitch.tv")
+            ))
+        );
+        assert_eq!(parsed.command(), Some("PRIVMSG"));
+        assert_eq!(parsed.params(), Some(vec!["#715209", "hello"]));
+    }
+
+    #[test]
+    fn normal_message_no_tags() {
+        let parsed =
+            Message::parse(":715209!715209@715209.tmi.twitch.tv PRIVMSG #715209 :hello").unwrap();
+
+        assert_eq!(parsed.tags(), None);
+        assert_eq!(
+            parsed.prefix(),
+            Some(Prefix::Nick(
+                "715209",
+                Some("715209"),
+                Some("715209.tmi.twitch.tv")
+            ))
+        );
+        assert_eq!(parsed.command(), Some("PRIVMSG"));
+        assert_eq!(parsed.params(), Some(vec!["#715209", "hello"]));
+    }
+
+    #[test]
+    fn ping() {
+        let parsed = Message::parse("PING :tmi.twitch.tv").unwrap();
+
+        assert_eq!(parsed.tags(), None);
+        assert_eq!(parsed.prefix(), None);
+        assert_eq!(parsed.command(), Some("PING"));
+        assert_eq!(parsed.params(), Some(vec!["tmi.twitch.tv"]));
+    }
+
+    #[test]
+    fn no_params() {
+        let parsed = Message::parse("@badge-info=;badges=;color=#008000;display-name=715209;emote-sets=0,33563,231890,300206296,300242181;user-id=21621987;user-type= :tmi.twitch.tv GLOBALUSERSTATE").unwrap();
+
+        assert!(parsed.tags().is_some());
+        assert_eq!(
+            parsed.prefix(),
+            Some(Prefix::Servername("tmi.twitch.tv"))
+        );
+        assert_eq!(parsed.command(), Some("GLOBALUSERSTATE"));
+        assert_eq!(parsed.params(), None);
+    }
+
+    #[test]
+    fn tags_no_prefix() {
+        let parsed = Message::parse("@badge-info=;badges=;color=#008000;display-name=715209;emote-sets=0,33563,231890,300206296,300242181;user-id=21621987;user-type= GLOBALUSERSTATE").unwrap();
+
+        assert!(parsed.tags().is_some());
+        assert_eq!(parsed.prefix(), None);
+        assert_eq!(parsed.command(), Some("GLOBALUSERSTATE"));
+        assert_eq!(parsed.params(), None);
+    }
+
+    #[test]
+    fn tags_and_params_no_prefix() {
+        let parsed = Message::parse("@badge-info=;badges=;color=#008000;display-name=715209;emote-sets=0,33563,231890,300206296,300242181;user-id=21621987;user-type= PRIVMSG #715209 :hello").unwrap();
+
+        assert!(parsed.tags().is_some());
+        assert_eq!(parsed.prefix(), None);
+        assert_eq!(parsed.command(), Some("PRIVMSG"));
+        assert_eq!(parsed.params(), Some(vec!["#715209", "hello"]));
+    }
+
+    #[test]
+    fn only_command() {
+        let parsed = Message::parse("PRIVMSG").unwrap();
+
+        assert_eq!(parsed.tags(), None);
+        assert_eq!(parsed.prefix(), None);
+        assert_eq!(parsed.command(), Some("PRIVMSG"));
+        assert_eq!(parsed.params(), None);
+    }
+
+    #[test]
+    fn nothing_to_parse() {
+        let parsed = Message::parse("");
+
+        assert_eq!(parsed, Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn only_tags() {
+        let parsed = Message::parse("@badge-info=;badges=;color=#008000;display-name=715209;emote-sets=0,33563,231890,300206296,300242181;user-id=21621987;user-type=");
+
+        assert!(matches!(parsed, Err(ParseError::MissingCommand { .. })));
+    }
+
+    #[test]
+    fn lenient_nick_only_user() {
+        let parsed = Message::parse(":715209!715209 PRIVMSG #715209 :hello").unwrap();
+
+        assert_eq!(
+            parsed.prefix(),
+            Some(Prefix::Nick("715209", Some("715209"), None))
+        );
+    }
+
+    #[test]
+    fn lenient_nick_only_host() {
+        let parsed = Message::parse(":715209@715209.tmi.twitch.tv PRIVMSG #715209 :hello").unwrap();
+
+        assert_eq!(
+            parsed.prefix(),
+            Some(Prefix::Nick("715209", None, Some("715209.tmi.twitch.tv")))
+        );
+    }
+
+    #[test]
+    fn malformed_prefix() {
+        let parsed = Message::parse(":715209!715209@715209@extra PRIVMSG #715209 :hello");
+
+        assert_eq!(parsed, Err(ParseError::MalformedPrefix { offset: 0 }));
+    }
+
+    #[test]
+    fn malformed_tag() {
+        let parsed = Message::parse("@=oops :tmi.twitch.tv PRIVMSG #715209 :hello");
+
+        assert_eq!(
+            parsed,
+            Err(ParseError::MalformedTag {
+                key: "".to_string(),
+                offset: 1
+            })
+        );
+    }
+
+    #[test]
+    fn round_trip_privmsg() {
+        let raw = ":715209!715209@715209.tmi.twitch.tv PRIVMSG #715209 :hello there";
+        let parsed = Message::parse(raw).unwrap();
+
+        assert_eq!(parsed.to_string(), format!("{}\r\n", raw));
+    }
+
+    #[test]
+    fn round_trip_ping() {
+        let raw = "PING :tmi.twitch.tv";
+        let parsed = Message::parse(raw).unwrap();
+
+        assert_eq!(parsed.to_string(), format!("{}\r\n", raw));
+    }
+
+    #[test]
+    fn round_trip_single_tag() {
+        let raw = "@id=8a90aa05-eea3-4699-84eb-1d4c65b85f94 :tmi.twitch.tv GLOBALUSERSTATE";
+        let parsed = Message::parse(raw).unwrap();
+
+        assert_eq!(parsed.to_string(), format!("{}\r\n", raw));
+    }
+
+    #[test]
+    fn round_trip_valueless_tag() {
+        let raw = "@+foo;id=123 :tmi.twitch.tv GLOBALUSERSTATE";
+        let parsed = Message::parse(raw).unwrap();
+
+        assert_eq!(parsed.tags().unwrap().get("+foo"), Some(None));
+        assert_eq!(parsed.to_string(), format!("{}\r\n", raw));
+    }
+
+    #[test]
+    fn multibyte_char_before_trailing_colon_does_not_panic() {
+        let parsed = Message::parse("CMD fooé:bar").unwrap();
+
+        assert_eq!(parsed.command(), Some("CMD"));
+        assert_eq!(parsed.params(), Some(vec!["fooé:bar"]));
+    }
+
+    #[test]
+    fn colon_in_middle_param_is_not_trailing_marker() {
+        let parsed = Message::parse("CMD a:b :trailing").unwrap();
+
+        assert_eq!(parsed.command(), Some("CMD"));
+        assert_eq!(parsed.params(), Some(vec!["a:b", "trailing"]));
+    }
+
+    #[test]
+    fn round_trip_escaped_tag_value() {
+        let raw = "@reply=hello\\sworld :tmi.twitch.tv GLOBALUSERSTATE";
+        let parsed = Message::parse(raw).unwrap();
+
+        assert_eq!(
+            parsed.tags().unwrap().get("reply"),
+            Some(Some("hello world".into()))
+        );
+        assert_eq!(parsed.to_string(), format!("{}\r\n", raw));
+    }
+
+    #[test]
+    fn to_owned_detaches_from_input() {
+        let raw = ":715209!715209@715209.tmi.twitch.tv PRIVMSG #715209 :hello".to_string();
+        let owned = Message::parse(&raw).unwrap().to_owned();
+        drop(raw);
+
+        assert_eq!(owned.command, Some("PRIVMSG".to_string()));
+        assert_eq!(
+            owned.params,
+            Some(vec!["#715209".to_string(), "hello".to_string()])
+        );
+    }
+}
@@ -0,0 +1,105 @@
+/// A standard IRC numeric reply code, so callers can `match` on
+/// `Numeric::ErrNicknameinuse` instead of the raw string `"433"`. Codes without a
+/// named variant here still round-trip through [`Numeric::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Numeric {
+    RplWelcome,
+    RplYourhost,
+    RplCreated,
+    RplMyinfo,
+    RplIsupport,
+    RplNamreply,
+    RplEndofnames,
+    RplMotdstart,
+    RplMotd,
+    RplEndofmotd,
+    ErrNosuchnick,
+    ErrNosuchchannel,
+    ErrUnknowncommand,
+    ErrNonicknamegiven,
+    ErrNicknameinuse,
+    ErrNotregistered,
+    ErrNeedmoreparams,
+    Unknown(u16),
+}
+
+impl Numeric {
+    /// The three-digit wire code for this reply.
+    pub fn code(&self) -> u16 {
+        match self {
+            Numeric::RplWelcome => 1,
+            Numeric::RplYourhost => 2,
+            Numeric::RplCreated => 3,
+            Numeric::RplMyinfo => 4,
+            Numeric::RplIsupport => 5,
+            Numeric::RplNamreply => 353,
+            Numeric::RplEndofnames => 366,
+            Numeric::RplMotdstart => 375,
+            Numeric::RplMotd => 372,
+            Numeric::RplEndofmotd => 376,
+            Numeric::ErrNosuchnick => 401,
+            Numeric::ErrNosuchchannel => 403,
+            Numeric::ErrUnknowncommand => 421,
+            Numeric::ErrNonicknamegiven => 431,
+            Numeric::ErrNicknameinuse => 433,
+            Numeric::ErrNotregistered => 451,
+            Numeric::ErrNeedmoreparams => 461,
+            Numeric::Unknown(code) => *code,
+        }
+    }
+}
+
+impl From<u16> for Numeric {
+    fn from(code: u16) -> Self {
+        match code {
+            1 => Numeric::RplWelcome,
+            2 => Numeric::RplYourhost,
+            3 => Numeric::RplCreated,
+            4 => Numeric::RplMyinfo,
+            5 => Numeric::RplIsupport,
+            353 => Numeric::RplNamreply,
+            366 => Numeric::RplEndofnames,
+            375 => Numeric::RplMotdstart,
+            372 => Numeric::RplMotd,
+            376 => Numeric::RplEndofmotd,
+            401 => Numeric::ErrNosuchnick,
+            403 => Numeric::ErrNosuchchannel,
+            421 => Numeric::ErrUnknowncommand,
+            431 => Numeric::ErrNonicknamegiven,
+            433 => Numeric::ErrNicknameinuse,
+            451 => Numeric::ErrNotregistered,
+            461 => Numeric::ErrNeedmoreparams,
+            other => Numeric::Unknown(other),
+        }
+    }
+}
+
+impl From<Numeric> for u16 {
+    fn from(numeric: Numeric) -> Self {
+        numeric.code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Numeric;
+
+    #[test]
+    fn known_code_resolves_to_named_variant() {
+        assert_eq!(Numeric::from(433), Numeric::ErrNicknameinuse);
+        assert_eq!(Numeric::from(353), Numeric::RplNamreply);
+    }
+
+    #[test]
+    fn unknown_code_falls_back() {
+        assert_eq!(Numeric::from(999), Numeric::Unknown(999));
+    }
+
+    #[test]
+    fn code_round_trips_through_conversions() {
+        let numeric = Numeric::from(1);
+
+        assert_eq!(numeric.code(), 1);
+        assert_eq!(u16::from(numeric), 1);
+    }
+}
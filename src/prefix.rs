@@ -0,0 +1,47 @@
+/// The `<prefix>` portion of a message: either the server that sent it, or
+/// the `<nick> ['!' <user>] ['@' <host>]` of the client that sent it. Per
+/// the grammar, `user` and `host` are each independently optional, so e.g.
+/// `nick!user` and `nick@host` are both valid nick prefixes on their own.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Prefix<'a> {
+    Servername(&'a str),
+    Nick(&'a str, Option<&'a str>, Option<&'a str>),
+}
+
+impl<'a> Prefix<'a> {
+    /// Parses the content between `:` and the following space, e.g.
+    /// `tmi.twitch.tv`, `715209!715209@715209.tmi.twitch.tv`, or a nick
+    /// prefix with only `!user` or only `@host` present.
+    pub(crate) fn parse(raw: &'a str) -> Prefix<'a> {
+        let bang = raw.find('!');
+        let at = raw.find('@');
+
+        match (bang, at) {
+            (Some(b), Some(a)) if b < a => {
+                Prefix::Nick(&raw[..b], Some(&raw[b + 1..a]), Some(&raw[a + 1..]))
+            }
+            (Some(b), None) => Prefix::Nick(&raw[..b], Some(&raw[b + 1..]), None),
+            (None, Some(a)) => Prefix::Nick(&raw[..a], None, Some(&raw[a + 1..])),
+            _ => Prefix::Servername(raw),
+        }
+    }
+
+    pub fn to_owned(&self) -> OwnedPrefix {
+        match self {
+            Prefix::Servername(name) => OwnedPrefix::Servername(name.to_string()),
+            Prefix::Nick(nick, user, host) => OwnedPrefix::Nick(
+                nick.to_string(),
+                user.map(|u| u.to_string()),
+                host.map(|h| h.to_string()),
+            ),
+        }
+    }
+}
+
+/// An owned, `'static` copy of [`Prefix`], for callers that can't (or don't
+/// want to) hold onto the original buffer.
+#[derive(Debug, PartialEq, Clone)]
+pub enum OwnedPrefix {
+    Servername(String),
+    Nick(String, Option<String>, Option<String>),
+}
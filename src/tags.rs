@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+
+/// A borrowed view over the `<tags>` portion of a message (everything
+/// between the leading `@` and the following space). Key/value pairs are
+/// split lazily on access instead of being eagerly collected into a map, so
+/// a caller that only needs one tag doesn't pay for the rest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tags<'a>(&'a str);
+
+impl<'a> Tags<'a> {
+    pub(crate) fn new(raw: &'a str) -> Tags<'a> {
+        Tags(raw)
+    }
+
+    /// Iterates the tags in source order as `(key, value)` pairs, with
+    /// values unescaped per the IRCv3 `<escaped_value>` grammar. A tag with
+    /// no `=value` at all yields `None`; a tag with an explicit but empty
+    /// `=` yields `Some` of an empty string, so the two forms don't collapse
+    /// into each other on round-trip.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, Option<Cow<'a, str>>)> + 'a {
+        self.0.split(';').map(|kv| {
+            let mut parts = kv.splitn(2, '=');
+            let key = parts.next().unwrap_or("");
+            let value = parts.next().map(unescape);
+
+            (key, value)
+        })
+    }
+
+    /// Looks up a single tag by key. Returns `None` if the key isn't
+    /// present at all, or `Some(None)` if it's present with no value.
+    pub fn get(&self, key: &str) -> Option<Option<Cow<'a, str>>> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+}
+
+/// Decodes the IRCv3 tag value escape sequences: `\:` -> `;`, `\s` -> space,
+/// `\\` -> `\`, `\r` -> CR, `\n` -> LF. A trailing lone `\` is dropped, and
+/// any other `\x` decodes to just `x`. Returns a borrowed slice when there's
+/// nothing to unescape.
+fn unescape(raw: &str) -> Cow<'_, str> {
+    if !raw.contains('\\') {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some(':') => out.push(';'),
+            Some('s') => out.push(' '),
+            Some('\\') => out.push('\\'),
+            Some('r') => out.push('\r'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+/// Encodes a tag value for the wire, the inverse of [`unescape`]: `;` ->
+/// `\:`, space -> `\s`, `\` -> `\\`, CR -> `\r`, LF -> `\n`. Other bytes are
+/// left untouched.
+pub(crate) fn escape(raw: &str) -> Cow<'_, str> {
+    if !raw.contains([';', ' ', '\\', '\r', '\n']) {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut out = String::with_capacity(raw.len());
+
+    for ch in raw.chars() {
+        match ch {
+            ';' => out.push_str("\\:"),
+            ' ' => out.push_str("\\s"),
+            '\\' => out.push_str("\\\\"),
+            '\r' => out.push_str("\\r"),
+            '\n' => out.push_str("\\n"),
+            other => out.push(other),
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, unescape, Tags};
+    use std::borrow::Cow;
+
+    #[test]
+    fn get_present_with_value() {
+        let tags = Tags::new("badge-info=;color=#008000;id=abc");
+
+        assert_eq!(tags.get("color"), Some(Some(Cow::Borrowed("#008000"))));
+    }
+
+    #[test]
+    fn get_present_empty_value() {
+        let tags = Tags::new("badge-info=;color=#008000");
+
+        assert_eq!(tags.get("badge-info"), Some(Some(Cow::Borrowed(""))));
+    }
+
+    #[test]
+    fn get_present_no_equals() {
+        let tags = Tags::new("+foo;color=#008000");
+
+        assert_eq!(tags.get("+foo"), Some(None));
+    }
+
+    #[test]
+    fn get_missing() {
+        let tags = Tags::new("color=#008000");
+
+        assert_eq!(tags.get("badges"), None);
+    }
+
+    #[test]
+    fn unescapes_tag_value() {
+        let tags = Tags::new("reply=hello\\sworld;ts=12\\:30\\\\done");
+
+        assert_eq!(tags.get("reply"), Some(Some(Cow::Borrowed("hello world"))));
+        assert_eq!(tags.get("ts"), Some(Some(Cow::Borrowed("12;30\\done"))));
+    }
+
+    #[test]
+    fn unescape_drops_trailing_lone_backslash() {
+        assert_eq!(unescape("abc\\"), "abc");
+    }
+
+    #[test]
+    fn unescape_unknown_escape_decodes_to_literal() {
+        assert_eq!(unescape("a\\xb"), "axb");
+    }
+
+    #[test]
+    fn escape_is_inverse_of_unescape() {
+        let raw = "hello world; \\ done\r\n";
+        let escaped = escape(raw);
+
+        assert_eq!(unescape(&escaped), raw);
+    }
+}